@@ -2,47 +2,77 @@
 use std::mem::ManuallyDrop;
 use std::ptr;
 
-pub struct ArrayIntoIter<T> {
-    array: [ManuallyDrop<T>; 3],
-    index: usize,
+pub struct ArrayIntoIter<T, const N: usize> {
+    array: [ManuallyDrop<T>; N],
+    // The half-open range `front..back` of `array` that is still alive.
+    front: usize,
+    back: usize,
 }
 
-impl<T> ArrayIntoIter<T> {
-    pub fn new(array: [T; 3]) -> Self {
-        let [a, b, c] = array;
-        let wrap = ManuallyDrop::new;
+impl<T, const N: usize> ArrayIntoIter<T, N> {
+    pub fn new(array: [T; N]) -> Self {
         ArrayIntoIter {
-            array: [wrap(a), wrap(b), wrap(c)],
-            index: 0,
+            array: array.map(ManuallyDrop::new),
+            front: 0,
+            back: N,
         }
     }
 }
 
-impl<T> Iterator for ArrayIntoIter<T> {
+impl<T, const N: usize> Iterator for ArrayIntoIter<T, N> {
     type Item = T;
 
     fn next(&mut self) -> Option<T> {
-        match self.index {
-            3 => None,
-            i => {
-                self.index += 1;
-                Some(ManuallyDrop::into_inner(unsafe { ptr::read(&self.array[i]) }))
-            }
+        if self.front == self.back {
+            return None;
         }
+        let item = unsafe { ptr::read(&self.array[self.front]) };
+        self.front += 1;
+        Some(ManuallyDrop::into_inner(item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for ArrayIntoIter<T, N> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        let item = unsafe { ptr::read(&self.array[self.back]) };
+        Some(ManuallyDrop::into_inner(item))
     }
 }
 
-impl<T> Drop for ArrayIntoIter<T> {
+impl<T, const N: usize> ExactSizeIterator for ArrayIntoIter<T, N> {
+    fn len(&self) -> usize {
+        self.back - self.front
+    }
+}
+
+impl<T, const N: usize> Drop for ArrayIntoIter<T, N> {
     fn drop(&mut self) {
-        // Run to completion
-        self.for_each(drop);
+        // Drop exactly the still-alive `front..back` range directly through
+        // the slice, once, rather than running `next()`/`next_back()` to
+        // completion. That keeps drop correct no matter which end consumption
+        // stopped at, and `ptr::drop_in_place` on a slice already has its own
+        // panic guard that keeps dropping the remaining elements if an
+        // earlier one's destructor panics, so this doesn't leak the tail of
+        // the array on unwind either.
+        let remaining = &mut self.array[self.front..self.back] as *mut [ManuallyDrop<T>] as *mut [T];
+        self.front = self.back;
+        unsafe { ptr::drop_in_place(remaining) };
     }
 }
 //------------------------------------------------------------
 
 mod util;
 
-use crate::util::DropLog;
+use crate::util::{DropLog, DropOrderCollector, Id, PanicTrigger, PrintOnDrop};
 
 #[test]
 fn no_iteration() {
@@ -51,7 +81,9 @@ fn no_iteration() {
         let array = [log.wrap(1), log.wrap(2), log.wrap(3)];
         let _ = ArrayIntoIter::new(array);
     }
-    assert_eq!(log.read(), vec![1, 2, 3])
+    assert_eq!(log.read(), vec![1, 2, 3]);
+    assert!(log.leaked().is_empty());
+    assert!(log.double_dropped().is_empty());
 }
 
 #[test]
@@ -63,7 +95,9 @@ fn partial_iter() {
         assert_eq!(iter.next().unwrap(), 1);
         assert_eq!(iter.next().unwrap(), 2);
     }
-    assert_eq!(log.read(), vec![1, 2, 3])
+    assert_eq!(log.read(), vec![1, 2, 3]);
+    assert!(log.leaked().is_empty());
+    assert!(log.double_dropped().is_empty());
 }
 
 #[test]
@@ -78,5 +112,125 @@ fn over_iter() {
         assert!(iter.next().is_none());
         assert!(iter.next().is_none());
     }
-    assert_eq!(log.read(), vec![1, 2, 3])
+    assert_eq!(log.read(), vec![1, 2, 3]);
+    assert!(log.leaked().is_empty());
+    assert!(log.double_dropped().is_empty());
+}
+
+#[test]
+fn forgotten_value_is_leaked() {
+    let log = DropLog::new();
+    let guard = log.wrap(4);
+    let id = guard.id();
+    std::mem::forget(guard);
+
+    assert_eq!(log.leaked(), vec![id]);
+    assert!(log.double_dropped().is_empty());
+}
+
+#[test]
+fn panic_during_drop_does_not_leak_the_rest() {
+    let log = DropLog::new();
+    let panics = PanicTrigger::new(2);
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let array = [panics.wrap(&log, 1), panics.wrap(&log, 2), panics.wrap(&log, 3)];
+        let mut iter = ArrayIntoIter::new(array);
+        iter.next();
+        // `iter` is dropped here with 2 elements left (index 1 and 2). The
+        // first of those panics on drop; if `Drop` for `ArrayIntoIter` still
+        // ran `for_each(drop)`, the last element would never be reached.
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(log.leaked(), Vec::<Id>::new());
+}
+
+#[test]
+fn mixed_end_exhaustion() {
+    let log = DropLog::new();
+    {
+        let array = [log.wrap(1), log.wrap(2), log.wrap(3), log.wrap(4)];
+        let mut iter = ArrayIntoIter::new(array);
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next().unwrap(), 1);
+        assert_eq!(iter.next_back().unwrap(), 4);
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_back().unwrap(), 3);
+        assert_eq!(iter.next().unwrap(), 2);
+        assert_eq!(iter.len(), 0);
+        assert!(iter.next().is_none());
+        assert!(iter.next_back().is_none());
+    }
+    assert_eq!(log.read(), vec![1, 4, 3, 2]);
+    assert!(log.leaked().is_empty());
+    assert!(log.double_dropped().is_empty());
+}
+
+#[test]
+fn mixed_end_partial_drop() {
+    let log = DropLog::new();
+    {
+        let array = [log.wrap(1), log.wrap(2), log.wrap(3), log.wrap(4), log.wrap(5)];
+        let mut iter = ArrayIntoIter::new(array);
+        assert_eq!(iter.next().unwrap(), 1);
+        assert_eq!(iter.next_back().unwrap(), 5);
+        assert_eq!(iter.len(), 3);
+        // `iter` is dropped here with elements 2, 3 and 4 (the `front..back`
+        // range) still alive.
+    }
+    assert_eq!(log.read(), vec![1, 5, 2, 3, 4]);
+    assert!(log.leaked().is_empty());
+    assert!(log.double_dropped().is_empty());
+}
+
+#[test]
+fn remaining_elements_drop_in_forward_order() {
+    let order = DropOrderCollector::new();
+    {
+        let array = [order.print(1), order.print(2), order.print(3), order.print(4)];
+        let mut iter = ArrayIntoIter::new(array);
+        iter.next();
+        iter.next_back();
+        // `iter` drops here with `2` and `3` (the `front..back` range) still
+        // alive; they must drop in forward order, not reverse.
+    }
+    order.assert_order(&[1, 4, 2, 3]);
+}
+
+fn consume_then_return(array: [PrintOnDrop; 3], take: usize) {
+    let mut iter = ArrayIntoIter::new(array);
+    let mut taken = 0;
+    while let Some(item) = iter.next() {
+        drop(item);
+        taken += 1;
+        if taken == take {
+            // Early return: `iter` drops here with the elements after the
+            // first `take` still unconsumed.
+            return;
+        }
+    }
+}
+
+#[test]
+fn array_argument_drops_in_order_on_early_return() {
+    let order = DropOrderCollector::new();
+    let array = [order.print(1), order.print(2), order.print(3)];
+    consume_then_return(array, 1);
+    order.assert_order(&[1, 2, 3]);
+}
+
+#[test]
+fn push_marks_a_milestone_between_consumption_and_drop() {
+    let order = DropOrderCollector::new();
+    {
+        let array = [order.print(1), order.print(2), order.print(3)];
+        let mut iter = ArrayIntoIter::new(array);
+        iter.next();
+        // Milestone: `1` has already dropped (it was read out of the array
+        // and its temporary dropped at the end of the previous statement),
+        // but `2` and `3` haven't yet — they're still owned by `iter`.
+        order.push(99);
+    }
+    order.assert_order(&[1, 99, 2, 3]);
 }
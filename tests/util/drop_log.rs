@@ -2,39 +2,98 @@ use std::rc::Rc;
 use std::cell::RefCell;
 use std::{ops, fmt};
 
+/// Identifies a single value handed out by `DropLog::wrap`, in the order it was created.
+pub type Id = u64;
+
+struct Inner<T> {
+    log: Vec<T>,
+    next_id: Id,
+    created: Vec<Id>,
+    dropped: Vec<Id>,
+}
+
 pub struct DropLog<T> {
-    log: Rc<RefCell<Vec<T>>>,
+    inner: Rc<RefCell<Inner<T>>>,
 }
 
 pub struct LogOnDrop<T> {
+    id: Id,
     value: Option<T>,
-    log: Rc<RefCell<Vec<T>>>,
+    inner: Rc<RefCell<Inner<T>>>,
 }
 
 impl<T> Drop for LogOnDrop<T> {
     fn drop(&mut self) {
-        self.log.borrow_mut().push(self.value.take().unwrap())
+        let mut inner = self.inner.borrow_mut();
+        inner.dropped.push(self.id);
+        if let Some(value) = self.value.take() {
+            inner.log.push(value);
+        }
     }
 }
 
 impl<T> DropLog<T> {
     pub fn new() -> Self
     { DropLog {
-        log: Rc::new(RefCell::new(vec![])),
+        inner: Rc::new(RefCell::new(Inner {
+            log: vec![],
+            next_id: 0,
+            created: vec![],
+            dropped: vec![],
+        })),
     }}
 
-    pub fn wrap(&self, value: T) -> LogOnDrop<T>
-    { LogOnDrop {
-        value: Some(value),
-        log: self.log.clone(),
-    }}
+    pub fn wrap(&self, value: T) -> LogOnDrop<T> {
+        let mut inner = self.inner.borrow_mut();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.created.push(id);
+        drop(inner);
+        LogOnDrop {
+            id,
+            value: Some(value),
+            inner: self.inner.clone(),
+        }
+    }
 
     // NOTE: Reads to Vec so that the RefCell lock can be released.
     /// Read the log of all values that were dropped after
     /// passing through `self.wrap()`.
     pub fn read(&self) -> Vec<T>
     where T: Clone,
-    { self.log.borrow().to_vec() }
+    { self.inner.borrow().log.to_vec() }
+
+    /// Ids of values that were `wrap()`ed but never reached `LogOnDrop::drop`
+    /// (e.g. because they were leaked with `mem::forget`), in creation order.
+    pub fn leaked(&self) -> Vec<Id> {
+        let inner = self.inner.borrow();
+        inner.created.iter()
+            .copied()
+            .filter(|id| !inner.dropped.contains(id))
+            .collect()
+    }
+
+    /// Ids whose `LogOnDrop::drop` ran more than once, in the order the
+    /// repeat drop was observed.
+    pub fn double_dropped(&self) -> Vec<Id> {
+        let inner = self.inner.borrow();
+        let mut seen = vec![false; inner.created.len()];
+        let mut doubled = vec![];
+        for &id in &inner.dropped {
+            let seen = &mut seen[id as usize];
+            if *seen {
+                doubled.push(id);
+            }
+            *seen = true;
+        }
+        doubled
+    }
+}
+
+impl<T> LogOnDrop<T> {
+    /// The id assigned to this value by `DropLog::wrap`.
+    pub fn id(&self) -> Id
+    { self.id }
 }
 
 impl<T> ops::Deref for LogOnDrop<T> {
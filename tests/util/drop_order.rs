@@ -0,0 +1,47 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+
+/// Collects the order in which numbered events occur, so that a test can
+/// assert on drop order instead of merely on *which* values were dropped.
+///
+/// Unlike `DropLog`, which records the values that passed through it,
+/// `DropOrderCollector` records caller-chosen numbers, which makes it
+/// convenient to annotate several different kinds of event (drops of
+/// several unrelated locals, or both drops and plain non-drop milestones)
+/// on a single shared timeline.
+pub struct DropOrderCollector {
+    order: Rc<RefCell<Vec<u32>>>,
+}
+
+/// A guard returned by `DropOrderCollector::print` that records its number
+/// into the collector's order when dropped.
+pub struct PrintOnDrop {
+    n: u32,
+    order: Rc<RefCell<Vec<u32>>>,
+}
+
+impl Drop for PrintOnDrop {
+    fn drop(&mut self) {
+        self.order.borrow_mut().push(self.n);
+    }
+}
+
+impl DropOrderCollector {
+    pub fn new() -> Self
+    { DropOrderCollector {
+        order: Rc::new(RefCell::new(vec![])),
+    }}
+
+    /// Returns a guard that records `n` at the point it is dropped.
+    pub fn print(&self, n: u32) -> PrintOnDrop
+    { PrintOnDrop { n, order: self.order.clone() } }
+
+    /// Records `n` immediately, for milestones that aren't tied to a drop.
+    pub fn push(&self, n: u32)
+    { self.order.borrow_mut().push(n); }
+
+    #[track_caller]
+    pub fn assert_order(&self, expected: &[u32]) {
+        assert_eq!(&self.order.borrow()[..], expected);
+    }
+}
@@ -0,0 +1,7 @@
+mod drop_log;
+mod drop_order;
+mod panic_on_drop;
+
+pub use drop_log::{DropLog, Id, LogOnDrop};
+pub use drop_order::{DropOrderCollector, PrintOnDrop};
+pub use panic_on_drop::PanicTrigger;
@@ -0,0 +1,56 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use crate::util::{DropLog, LogOnDrop};
+
+/// Arms a shared counter that makes the `N`th value dropped through it panic.
+///
+/// Every value produced by [`PanicTrigger::wrap`] shares the same counter, so
+/// whichever of several guards happens to be dropped 2nd is the one that
+/// panics (e.g. the 2nd element of an array whose elements drop in order).
+pub struct PanicTrigger {
+    count: Rc<Cell<usize>>,
+    panic_at: usize,
+}
+
+impl PanicTrigger {
+    /// `panic_at` is 1-indexed: `PanicTrigger::new(2)` panics on the 2nd drop.
+    pub fn new(panic_at: usize) -> Self
+    { PanicTrigger {
+        count: Rc::new(Cell::new(0)),
+        panic_at,
+    }}
+
+    pub fn wrap<T>(&self, log: &DropLog<T>, value: T) -> PanicOnDrop<T>
+    { PanicOnDrop {
+        guard: Some(log.wrap(value)),
+        count: self.count.clone(),
+        panic_at: self.panic_at,
+    }}
+}
+
+/// A value that logs itself to a `DropLog` like any other, but panics partway
+/// through `drop` once its shared `PanicTrigger` counter reaches the armed count.
+///
+/// This is used to check that destructors further down the line still run
+/// (and get a chance to log themselves) when an earlier one panics, which is
+/// exactly the property a naive `Drop` impl built on `Iterator::for_each` lacks.
+pub struct PanicOnDrop<T> {
+    guard: Option<LogOnDrop<T>>,
+    count: Rc<Cell<usize>>,
+    panic_at: usize,
+}
+
+impl<T> Drop for PanicOnDrop<T> {
+    fn drop(&mut self) {
+        // Drop the inner guard first so it still logs itself even though we're
+        // about to panic.
+        drop(self.guard.take());
+
+        let n = self.count.get() + 1;
+        self.count.set(n);
+        if n == self.panic_at {
+            panic!("PanicOnDrop: panicking on drop #{}", n);
+        }
+    }
+}